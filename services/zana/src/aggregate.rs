@@ -0,0 +1,305 @@
+/*!
+Queries book data from both [OpenLibrary](crate::openlibrary) and
+[Google Books](crate::googlebooks) concurrently, and merges the results into a single
+[`Book`](struct@Book) using the [`MergedClient`](struct@MergedClient) implementation of
+[`BookClient`](trait@BookClient).
+
+### Example
+
+```
+use zana::{Book, BookClient, ClientError};
+use zana::aggregate::MergedClient;
+use zana::googlebooks;
+use zana::openlibrary;
+use zana::retry::RetryConfig;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let isbn = "9780316387316";
+
+    let openlibrary_client =
+        openlibrary::Client::new("https://openlibrary.org", RetryConfig::default())?;
+    let googlebooks_client = googlebooks::Client::new(
+        "YOUR-API-KEY",
+        "https://www.googleapis.com",
+        RetryConfig::default(),
+    )?;
+    let client = MergedClient::new(openlibrary_client, googlebooks_client);
+
+    match client.book_by_isbn(isbn).await {
+        Ok(book) => println!("book found ({}: {:?})", isbn, &book),
+        Err(err) => eprintln!("could not fetch book by ISBN {:?}", err),
+    };
+    Ok(())
+}
+```
+ */
+use async_trait::async_trait;
+
+use crate::{googlebooks, openlibrary, Book, BookClient, ClientError, Identifiers};
+
+/// Client that queries [`openlibrary::Client`](struct@openlibrary::Client) and
+/// [`googlebooks::Client`](struct@googlebooks::Client) concurrently and merges the two
+/// results into a single, more complete [`Book`](struct@Book).
+///
+/// The two providers are queried in parallel with [`tokio::join!`], so this client is only
+/// available when the `blocking` feature is disabled.
+///
+/// Merge rules:
+/// - `description`, `page_count`, `title` and `authors` are taken from whichever provider
+///   returned a non-empty value, preferring the OpenLibrary result on a tie
+/// - `rating` is taken from whichever provider has the larger `ratings_count`
+/// - `identifiers` are combined, preferring OpenLibrary's identifier for any field both
+///   providers returned
+/// - `provider_link` is always OpenLibrary's, since it is considered the canonical source
+///
+/// If only one provider returns a [Book](struct@Book), that book is returned as-is.
+/// An error is only returned if both providers fail.
+pub struct MergedClient {
+    openlibrary_client: openlibrary::Client,
+    googlebooks_client: googlebooks::Client,
+}
+
+impl MergedClient {
+    /// Returns a new client that merges results from the given OpenLibrary and Google Books
+    /// clients.
+    pub fn new(openlibrary_client: openlibrary::Client, googlebooks_client: googlebooks::Client) -> Self {
+        Self {
+            openlibrary_client,
+            googlebooks_client,
+        }
+    }
+
+    fn merge_results(
+        openlibrary_result: Result<Book, ClientError>,
+        googlebooks_result: Result<Book, ClientError>,
+    ) -> Result<Book, ClientError> {
+        match (openlibrary_result, googlebooks_result) {
+            (Ok(openlibrary_book), Ok(googlebooks_book)) => {
+                Ok(Self::merge_books(openlibrary_book, googlebooks_book))
+            }
+            (Ok(book), Err(_)) => Ok(book),
+            (Err(_), Ok(book)) => Ok(book),
+            (Err(err), Err(_)) => Err(err),
+        }
+    }
+
+    fn merge_books(openlibrary_book: Book, googlebooks_book: Book) -> Book {
+        let description = if !openlibrary_book.description.is_empty() {
+            openlibrary_book.description
+        } else {
+            googlebooks_book.description
+        };
+
+        let page_count = if openlibrary_book.page_count != 0 {
+            openlibrary_book.page_count
+        } else {
+            googlebooks_book.page_count
+        };
+
+        let rating = match (openlibrary_book.rating, googlebooks_book.rating) {
+            (Some(openlibrary_rating), Some(googlebooks_rating)) => {
+                if openlibrary_rating.ratings_count >= googlebooks_rating.ratings_count {
+                    Some(openlibrary_rating)
+                } else {
+                    Some(googlebooks_rating)
+                }
+            }
+            (Some(rating), None) | (None, Some(rating)) => Some(rating),
+            (None, None) => None,
+        };
+
+        let title = if !openlibrary_book.title.is_empty() {
+            openlibrary_book.title
+        } else {
+            googlebooks_book.title
+        };
+
+        let authors = if !openlibrary_book.authors.is_empty() {
+            openlibrary_book.authors
+        } else {
+            googlebooks_book.authors
+        };
+
+        let identifiers = Identifiers {
+            isbn_10: openlibrary_book.identifiers.isbn_10.or(googlebooks_book.identifiers.isbn_10),
+            isbn_13: openlibrary_book.identifiers.isbn_13.or(googlebooks_book.identifiers.isbn_13),
+            olid: openlibrary_book.identifiers.olid.or(googlebooks_book.identifiers.olid),
+            google_volume_id: openlibrary_book
+                .identifiers
+                .google_volume_id
+                .or(googlebooks_book.identifiers.google_volume_id),
+        };
+
+        Book {
+            page_count,
+            description,
+            provider_link: openlibrary_book.provider_link,
+            rating,
+            title,
+            authors,
+            identifiers,
+        }
+    }
+}
+
+#[async_trait]
+impl BookClient for MergedClient {
+    /// Returns a book by ISBN, merged from both OpenLibrary and Google Books.
+    ///
+    /// Both providers are queried concurrently. If one fails, the other's result is returned;
+    /// an error is only returned if both fail.
+    async fn book_by_isbn(&self, isbn: &str) -> Result<Book, ClientError> {
+        let (openlibrary_result, googlebooks_result) = tokio::join!(
+            self.openlibrary_client.book_by_isbn(isbn),
+            self.googlebooks_client.book_by_isbn(isbn)
+        );
+        Self::merge_results(openlibrary_result, googlebooks_result)
+    }
+
+    /// Returns a book by author and title.
+    ///
+    /// OpenLibrary does not support this lookup, so only Google Books is queried.
+    async fn book(&self, author: &str, title: &str) -> Result<Book, ClientError> {
+        self.googlebooks_client.book(author, title).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rating;
+
+    fn book(description: &str, page_count: u32, title: &str, authors: Vec<&str>, identifiers: Identifiers) -> Book {
+        Book::new(
+            page_count,
+            description,
+            "https://example.com",
+            title,
+            authors.into_iter().map(String::from).collect(),
+            identifiers,
+        )
+    }
+
+    #[test]
+    fn merge_books_prefers_openlibrary_on_a_tie() {
+        let openlibrary_book = book("ol description", 100, "OL Title", vec!["OL Author"], Identifiers::default());
+        let googlebooks_book = book("gb description", 200, "GB Title", vec!["GB Author"], Identifiers::default());
+
+        let merged = MergedClient::merge_books(openlibrary_book, googlebooks_book);
+
+        assert_eq!(merged.description, "ol description");
+        assert_eq!(merged.page_count, 100);
+        assert_eq!(merged.title, "OL Title");
+        assert_eq!(merged.authors, vec![String::from("OL Author")]);
+    }
+
+    #[test]
+    fn merge_books_falls_back_to_googlebooks_for_empty_openlibrary_fields() {
+        let openlibrary_book = book("", 0, "", vec![], Identifiers::default());
+        let googlebooks_book = book("gb description", 200, "GB Title", vec!["GB Author"], Identifiers::default());
+
+        let merged = MergedClient::merge_books(openlibrary_book, googlebooks_book);
+
+        assert_eq!(merged.description, "gb description");
+        assert_eq!(merged.page_count, 200);
+        assert_eq!(merged.title, "GB Title");
+        assert_eq!(merged.authors, vec![String::from("GB Author")]);
+    }
+
+    #[test]
+    fn merge_books_prefers_the_rating_with_the_larger_ratings_count() {
+        let mut openlibrary_book = book("", 0, "", vec![], Identifiers::default());
+        openlibrary_book.rating = Some(Rating::new(4.0, 10));
+        let mut googlebooks_book = book("", 0, "", vec![], Identifiers::default());
+        googlebooks_book.rating = Some(Rating::new(3.0, 100));
+
+        let merged = MergedClient::merge_books(openlibrary_book, googlebooks_book);
+
+        assert_eq!(merged.rating, Some(Rating::new(3.0, 100)));
+    }
+
+    #[test]
+    fn merge_books_takes_the_only_rating_present() {
+        let openlibrary_book = book("", 0, "", vec![], Identifiers::default());
+        let mut googlebooks_book = book("", 0, "", vec![], Identifiers::default());
+        googlebooks_book.rating = Some(Rating::new(3.0, 100));
+
+        let merged = MergedClient::merge_books(openlibrary_book, googlebooks_book);
+
+        assert_eq!(merged.rating, Some(Rating::new(3.0, 100)));
+    }
+
+    #[test]
+    fn merge_books_prefers_openlibrary_identifiers_but_fills_in_the_rest() {
+        let openlibrary_book = book(
+            "",
+            0,
+            "",
+            vec![],
+            Identifiers::new(Some(String::from("ol-isbn-10")), None, Some(String::from("OL123W")), None),
+        );
+        let googlebooks_book = book(
+            "",
+            0,
+            "",
+            vec![],
+            Identifiers::new(
+                Some(String::from("gb-isbn-10")),
+                Some(String::from("gb-isbn-13")),
+                None,
+                Some(String::from("gb-volume-id")),
+            ),
+        );
+
+        let merged = MergedClient::merge_books(openlibrary_book, googlebooks_book);
+
+        assert_eq!(
+            merged.identifiers,
+            Identifiers::new(
+                Some(String::from("ol-isbn-10")),
+                Some(String::from("gb-isbn-13")),
+                Some(String::from("OL123W")),
+                Some(String::from("gb-volume-id")),
+            )
+        );
+    }
+
+    #[test]
+    fn merge_books_uses_openlibrary_provider_link() {
+        let openlibrary_book = book("", 0, "", vec![], Identifiers::default());
+        let mut googlebooks_book = book("", 0, "", vec![], Identifiers::default());
+        googlebooks_book.provider_link = String::from("https://books.google.com");
+
+        let merged = MergedClient::merge_books(openlibrary_book, googlebooks_book);
+
+        assert_eq!(merged.provider_link, "https://example.com");
+    }
+
+    #[test]
+    fn merge_results_merges_when_both_providers_succeed() {
+        let openlibrary_book = book("ol description", 0, "", vec![], Identifiers::default());
+        let googlebooks_book = book("", 0, "", vec![], Identifiers::default());
+
+        let merged = MergedClient::merge_results(Ok(openlibrary_book), Ok(googlebooks_book)).unwrap();
+
+        assert_eq!(merged.description, "ol description");
+    }
+
+    #[test]
+    fn merge_results_returns_the_ok_side_when_one_provider_fails() {
+        let googlebooks_book = book("gb description", 0, "", vec![], Identifiers::default());
+
+        let merged =
+            MergedClient::merge_results(Err(ClientError::NotFound), Ok(googlebooks_book)).unwrap();
+
+        assert_eq!(merged.description, "gb description");
+    }
+
+    #[test]
+    fn merge_results_returns_an_error_when_both_providers_fail() {
+        let result = MergedClient::merge_results(Err(ClientError::NotFound), Err(ClientError::RateLimitExceeded));
+
+        assert!(matches!(result, Err(ClientError::NotFound)));
+    }
+}
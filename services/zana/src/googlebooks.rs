@@ -2,14 +2,26 @@
 Queries book data from Google Books API using the [`Client`](struct@Client)
 implementation of [`BookClient`](trait@BookClient).
 
-It queries the `volumes` endpoints to retrieve data about a book, its author and ratings.
+It queries the `volumes` endpoints to retrieve data about a book, its title, authors and
+ratings.
 
 See example [here](../index.html#example).
+
+## Bookshelves
+
+[`Client::with_oauth`](Client::with_oauth) authenticates with an OAuth2 access token
+instead of an API key, which allows the [bookshelves](mod@bookshelves) module to list and
+query the authenticated user's personal library, something the public `volumes` endpoint
+cannot do.
  */
+#[cfg(not(feature = "blocking"))]
 use async_trait::async_trait;
 use serde::Deserialize;
 
-use crate::{create_http_client, Book, BookClient, ClientError, Rating};
+use crate::retry::{self, RetryConfig};
+use crate::{create_http_client, Book, BookClient, ClientError, HttpClient, Identifiers, Rating};
+
+pub mod bookshelves;
 
 const VOLUMES_PATH: &str = "/books/v1/volumes";
 
@@ -20,41 +32,111 @@ struct Volume {
 
 #[derive(Deserialize, Debug)]
 struct VolumeItem {
+    id: String,
     #[serde(rename(deserialize = "volumeInfo"))]
     info: VolumeInfo,
 }
 
 #[derive(Deserialize, Debug)]
 struct VolumeInfo {
-    #[serde(rename(deserialize = "title"))]
-    _title: String,
-    #[serde(rename(deserialize = "authors"))]
-    _authors: Vec<String>,
+    title: String,
+    #[serde(default)]
+    authors: Vec<String>,
+    #[serde(default)]
     description: String,
-    #[serde(rename(deserialize = "pageCount"))]
+    #[serde(default, rename(deserialize = "pageCount"))]
     page_count: u32,
-    #[serde(rename(deserialize = "averageRating"))]
+    #[serde(default, rename(deserialize = "averageRating"))]
     average_rating: f32,
-    #[serde(rename(deserialize = "ratingsCount"))]
+    #[serde(default, rename(deserialize = "ratingsCount"))]
     ratings_count: u32,
+    #[serde(default, rename(deserialize = "canonicalVolumeLink"))]
+    canonical_volume_link: String,
+    #[serde(default, rename(deserialize = "industryIdentifiers"))]
+    industry_identifiers: Vec<IndustryIdentifier>,
+}
+
+#[derive(Deserialize, Debug)]
+struct IndustryIdentifier {
+    #[serde(rename(deserialize = "type"))]
+    id_type: String,
+    identifier: String,
+}
+
+impl VolumeInfo {
+    fn isbn(&self, id_type: &str) -> Option<String> {
+        self.industry_identifiers
+            .iter()
+            .find(|industry_identifier| industry_identifier.id_type == id_type)
+            .map(|industry_identifier| industry_identifier.identifier.clone())
+    }
+}
+
+fn book_from_volume_item(volume_item: &VolumeItem) -> Book {
+    let volume_info = &volume_item.info;
+
+    let identifiers = Identifiers::new(
+        volume_info.isbn("ISBN_10"),
+        volume_info.isbn("ISBN_13"),
+        None,
+        Some(volume_item.id.clone()),
+    );
+
+    let rating = Rating::new(volume_info.average_rating, volume_info.ratings_count);
+    Book::new_with_rating(
+        volume_info.page_count,
+        &volume_info.description,
+        &volume_info.canonical_volume_link,
+        &volume_info.title,
+        volume_info.authors.clone(),
+        identifiers,
+        rating,
+    )
+}
+
+/// How a [`Client`](struct@Client) authenticates with Google Books.
+enum Auth {
+    /// Sent as the `key` query parameter, used for the public `volumes` endpoint.
+    ApiKey(String),
+    /// Sent as an `Authorization: Bearer` header, required for the `mylibrary` endpoints
+    /// exposed by the [bookshelves](mod@bookshelves) module.
+    OAuth(String),
 }
 
 /// Client used to retrieve data from Google Books API.
 pub struct Client {
-    api_key: String,
+    auth: Auth,
     api_url: String,
-    http_client: reqwest::Client,
+    http_client: HttpClient,
+    retry_config: RetryConfig,
 }
 
 impl Client {
     /// Returns a new client that will make requests using the given API key to
     /// the given API URL.
-    pub fn new(api_key: &str, api_url: &str) -> Result<Self, ClientError> {
+    ///
+    /// `retry_config` controls how the client retries requests that are rate limited or
+    /// fail transiently, see [RetryConfig](crate::retry::RetryConfig).
+    pub fn new(api_key: &str, api_url: &str, retry_config: RetryConfig) -> Result<Self, ClientError> {
+        Self::build(Auth::ApiKey(String::from(api_key)), api_url, retry_config)
+    }
+
+    /// Returns a new client authenticated with an OAuth2 access token, sent as an
+    /// `Authorization: Bearer` header on every request, instead of an API key.
+    ///
+    /// This is required to use the [bookshelves](mod@bookshelves) module, since the `mylibrary`
+    /// endpoints it queries operate on the authenticated user's personal library.
+    pub fn with_oauth(token: &str, api_url: &str, retry_config: RetryConfig) -> Result<Self, ClientError> {
+        Self::build(Auth::OAuth(String::from(token)), api_url, retry_config)
+    }
+
+    fn build(auth: Auth, api_url: &str, retry_config: RetryConfig) -> Result<Self, ClientError> {
         let http_client = create_http_client()?;
         Ok(Client {
-            api_key: String::from(api_key),
+            auth,
             api_url: String::from(api_url),
             http_client,
+            retry_config,
         })
     }
 
@@ -62,52 +144,85 @@ impl Client {
         if items.is_empty() {
             return Err(ClientError::NotFound);
         }
-
-        let volume_item = &items[0];
-        let volume_info = &volume_item.info;
-
-        let rating = Rating::new(volume_info.average_rating, volume_info.ratings_count);
-        Ok(Book::new_with_rating(
-            volume_info.page_count,
-            &volume_info.description,
-            rating,
-        ))
+        Ok(book_from_volume_item(&items[0]))
     }
 
+    #[maybe_async::maybe_async]
     async fn fetch_book(&self, query: &str) -> Result<Book, ClientError> {
-        let query_list: Vec<(&str, &str)> = vec![
-            ("key", &self.api_key),
-            ("maxResults", "1"),
-            ("fields", "items"),
-            ("q", query),
-        ];
-
-        let response = self
-            .http_client
-            .get(format!("{}{}", self.api_url, VOLUMES_PATH))
-            .header("Accept-Encoding", "gzip")
-            .query(&query_list)
-            .send()
-            .await?;
-
-        let status_code = response.status().as_u16();
-        if status_code == 429 || status_code == 403 {
-            return Err(ClientError::RateLimitExceeded);
-        } else if status_code < 200 || status_code >= 300 {
-            let response_body = response.text().await?;
-            return Err(ClientError::Http(status_code, response_body));
+        let mut query_list: Vec<(&str, &str)> = vec![("maxResults", "1"), ("fields", "items"), ("q", query)];
+        if let Auth::ApiKey(api_key) = &self.auth {
+            query_list.push(("key", api_key));
         }
 
-        let volume: Volume = response.json().await?;
+        let volume: Volume = self
+            .fetch_json(&format!("{}{}", self.api_url, VOLUMES_PATH), &query_list)
+            .await?;
 
         if let Some(items) = volume.items {
-            return self.create_book(items);
+            self.create_book(items)
+        } else {
+            Err(ClientError::NotFound)
+        }
+    }
+
+    /// Fetches and deserializes the given URL and query parameters, authenticating per
+    /// [`Auth`], and retrying on `429`/`503` responses and network/timeout errors,
+    /// per [RetryConfig](crate::retry::RetryConfig). A `403` response is not retried and is
+    /// returned as [`ClientError::RateLimitExceeded`].
+    #[maybe_async::maybe_async]
+    pub(crate) async fn fetch_json<T>(&self, url: &str, query: &[(&str, &str)]) -> Result<T, ClientError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let mut attempt = 0;
+        loop {
+            let mut request = self
+                .http_client
+                .get(url)
+                .header("Accept-Encoding", "gzip")
+                .query(query);
+            if let Auth::OAuth(token) = &self.auth {
+                request = request.bearer_auth(token);
+            }
+
+            let response = request.send().await;
+
+            let response = match response {
+                Ok(response) => response,
+                Err(_) if attempt < self.retry_config.max_retries => {
+                    retry::sleep(retry::backoff_delay(&self.retry_config, attempt)).await;
+                    attempt += 1;
+                    continue;
+                }
+                Err(err) => return Err(ClientError::InternalClient(err)),
+            };
+
+            let status_code = response.status().as_u16();
+            if status_code == 429 || status_code == 503 {
+                if attempt >= self.retry_config.max_retries {
+                    return Err(ClientError::RateLimitExceeded);
+                }
+                let delay = retry::retry_after_delay(response.headers())
+                    .unwrap_or_else(|| retry::backoff_delay(&self.retry_config, attempt));
+                retry::sleep(delay).await;
+                attempt += 1;
+                continue;
+            } else if status_code == 403 {
+                return Err(ClientError::RateLimitExceeded);
+            } else if status_code == 404 {
+                return Err(ClientError::NotFound);
+            } else if status_code < 200 || status_code >= 300 {
+                let response_body = response.text().await?;
+                return Err(ClientError::Http(status_code, response_body));
+            }
+
+            return Ok(response.json().await?);
         }
-        Err(ClientError::NotFound)
     }
 }
 
-#[async_trait]
+#[cfg_attr(not(feature = "blocking"), async_trait)]
+#[maybe_async::maybe_async]
 impl BookClient for Client {
     /// Returns a book by ISBN.
     ///
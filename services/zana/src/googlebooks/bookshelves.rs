@@ -0,0 +1,117 @@
+/*!
+Lists a user's bookshelves and the volumes on them, via Google Books' `mylibrary` endpoints.
+
+These endpoints require a [`Client`](super::Client) authenticated with
+[`Client::with_oauth`](super::Client::with_oauth), since they operate on the authenticated
+user's personal library rather than the public `volumes` endpoint.
+
+### Example
+
+```
+use zana::googlebooks::Client;
+use zana::retry::RetryConfig;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let token = "YOUR-OAUTH-TOKEN";
+    let client = Client::with_oauth(token, "https://www.googleapis.com", RetryConfig::default())?;
+
+    match client.bookshelves().await {
+        Ok(shelves) => {
+            for shelf in &shelves {
+                println!("shelf {} ({}): {} volume(s)", shelf.id, shelf.title, shelf.volumes.len())
+            }
+        }
+        Err(err) => eprintln!("could not fetch bookshelves {:?}", err),
+    };
+    Ok(())
+}
+```
+ */
+use serde::Deserialize;
+
+use crate::{Book, ClientError};
+
+use super::{Auth, Client, Volume};
+
+const BOOKSHELVES_PATH: &str = "/books/v1/mylibrary/bookshelves";
+
+#[derive(Deserialize, Debug)]
+struct BookshelvesResponse {
+    items: Vec<BookshelfItem>,
+}
+
+#[derive(Deserialize, Debug)]
+struct BookshelfItem {
+    id: u32,
+    title: String,
+}
+
+/// A bookshelf in a user's Google Books library, with the volumes on it.
+#[derive(Debug, PartialEq)]
+pub struct Shelf {
+    pub id: u32,
+    pub title: String,
+    pub volumes: Vec<Book>,
+}
+
+impl Client {
+    /// Returns the authenticated user's bookshelves, each with its volumes.
+    ///
+    /// This lists the user's shelves, then fetches each shelf's volumes in turn via
+    /// [`shelf_volumes`](Client::shelf_volumes).
+    ///
+    /// Returns [`ClientError::Unauthorized`] if this client was not built with
+    /// [`Client::with_oauth`](Client::with_oauth).
+    #[maybe_async::maybe_async]
+    pub async fn bookshelves(&self) -> Result<Vec<Shelf>, ClientError> {
+        self.require_oauth()?;
+
+        let response: BookshelvesResponse = self.fetch_json(&self.bookshelves_url(), &[]).await?;
+
+        let mut shelves = Vec::with_capacity(response.items.len());
+        for item in response.items {
+            let volumes = self.shelf_volumes(item.id).await?;
+            shelves.push(Shelf {
+                id: item.id,
+                title: item.title,
+                volumes,
+            });
+        }
+        Ok(shelves)
+    }
+
+    /// Returns the volumes on the bookshelf with the given id.
+    ///
+    /// Returns [`ClientError::Unauthorized`] if this client was not built with
+    /// [`Client::with_oauth`](Client::with_oauth).
+    #[maybe_async::maybe_async]
+    pub async fn shelf_volumes(&self, shelf_id: u32) -> Result<Vec<Book>, ClientError> {
+        self.require_oauth()?;
+
+        let url = format!("{}/{}/volumes", self.bookshelves_url(), shelf_id);
+        let volume: Volume = self.fetch_json(&url, &[]).await?;
+
+        Ok(volume
+            .items
+            .unwrap_or_default()
+            .iter()
+            .map(super::book_from_volume_item)
+            .collect())
+    }
+
+    fn bookshelves_url(&self) -> String {
+        format!("{}{}", self.api_url, BOOKSHELVES_PATH)
+    }
+
+    /// Returns [`ClientError::Unauthorized`] unless this client was built with
+    /// [`Client::with_oauth`](Client::with_oauth), since the `mylibrary` endpoints reject
+    /// API-key authentication with a 403 that would otherwise surface as
+    /// [`ClientError::RateLimitExceeded`].
+    fn require_oauth(&self) -> Result<(), ClientError> {
+        match self.auth {
+            Auth::OAuth(_) => Ok(()),
+            Auth::ApiKey(_) => Err(ClientError::Unauthorized),
+        }
+    }
+}
@@ -13,11 +13,16 @@ to retrieve data by ISBN of a book. In cases where no data is found by ISBN,
 then book title and author are used as a backup.
 [`Client`](struct@googlebooks::Client) is used to query data from Google Books API.
 
+[`Client::with_oauth`](googlebooks::Client::with_oauth) authenticates with an OAuth2 access
+token instead, which also allows querying the authenticated user's bookshelves through the
+[googlebooks::bookshelves](mod@googlebooks::bookshelves) module.
+
 ### Example
 
 ```
 use zana::{Book, BookClient, ClientError};
 use zana::googlebooks::Client;
+use zana::retry::RetryConfig;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -25,7 +30,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let api_key = "YOUR-API-KEY";
     let isbn = "9780316387316";
 
-    let client = Client::new(api_key, api_url)?;
+    let client = Client::new(api_key, api_url, RetryConfig::default())?;
 
     match client.book_by_isbn(isbn).await {
         Ok(book) => println!("book found ({}: {:?})", isbn, &book),
@@ -47,13 +52,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 ```
 use zana::{Book, BookClient, ClientError};
 use zana::openlibrary::Client;
+use zana::retry::RetryConfig;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let api_url = "https://openlibrary.org";
     let isbn = "9780316387316";
 
-    let client = Client::new(api_url)?;
+    let client = Client::new(api_url, RetryConfig::default())?;
 
     match client.book_by_isbn(isbn).await {
         Ok(book) => println!("book found ({}: {:?})", isbn, &book),
@@ -70,17 +76,50 @@ is returned from clients.
 
 For status codes that are not 200, [ClientError](enum@ClientError) is returned with more
 information about the source of the error.
+
+## Retries
+
+Both clients accept a [`RetryConfig`](struct@retry::RetryConfig) on construction, which
+controls how requests are retried when they are rate limited (`429`/`503`) or fail
+transiently. See the [retry](mod@retry) module for details.
+
+## Blocking usage
+
+By default both clients are async and require a tokio runtime. Enabling the `blocking`
+feature compiles [`googlebooks::Client`](struct@googlebooks::Client) and
+[`openlibrary::Client`](struct@openlibrary::Client) as synchronous clients instead,
+built on [reqwest::blocking](reqwest::blocking), with the same method signatures. This
+is useful for embedding zana in non-tokio contexts such as CLI tools or sync lambdas.
+
+[`aggregate::MergedClient`](struct@aggregate::MergedClient) queries both providers
+concurrently with [`tokio::join!`], so it is only available when `blocking` is disabled.
 */
 
 extern crate core;
 
 use std::time::Duration;
 
+#[cfg(not(feature = "blocking"))]
 use async_trait::async_trait;
 use thiserror::Error;
 
+#[cfg(not(feature = "blocking"))]
+pub mod aggregate;
 pub mod googlebooks;
 pub mod openlibrary;
+pub mod retry;
+
+/// The HTTP client used internally by [`googlebooks::Client`](struct@googlebooks::Client) and
+/// [`openlibrary::Client`](struct@openlibrary::Client).
+///
+/// This is [reqwest::Client](reqwest::Client) by default, or
+/// [reqwest::blocking::Client](reqwest::blocking::Client) when the `blocking` feature is enabled,
+/// so that both clients can be compiled either as async or synchronous without duplicating
+/// their implementation (see [maybe_async](https://docs.rs/maybe-async)).
+#[cfg(not(feature = "blocking"))]
+pub(crate) type HttpClient = reqwest::Client;
+#[cfg(feature = "blocking")]
+pub(crate) type HttpClient = reqwest::blocking::Client;
 
 /// An error that occurs for implementations of [BookClient][trait@BookClient].
 ///
@@ -102,6 +141,13 @@ pub enum ClientError {
     /// Occurs for any response that is not 200, 404 or 429 (403 included for some clients).
     #[error("generic http error that contains status code and response body")]
     Http(u16, String),
+    /// Occurs when an operation that requires OAuth2 authentication is attempted on a client
+    /// that was not built with [`Client::with_oauth`](crate::googlebooks::Client::with_oauth).
+    #[error("operation requires a client authenticated with an OAuth2 access token")]
+    Unauthorized,
+    /// Occurs when an operation is not supported by a client's underlying provider.
+    #[error("operation not supported: {0}")]
+    Unsupported(&'static str),
 }
 
 /// Book data retrieved from third-party services supported by the crate.
@@ -118,6 +164,9 @@ pub enum ClientError {
 /// [rating](struct@Book.rating) is optional, since in some cases books either may not have
 /// rating data available yet, or other third-party services that can be added in the future
 /// may not provide ratings at all.
+///
+/// [authors](struct@Book.authors) is empty, and [title](struct@Book.title) is an empty
+/// string, if not provided by the third-party service.
 #[derive(Debug, PartialEq)]
 pub struct Book {
     /// Number of pages, 0 if not provided by the third-party service
@@ -127,6 +176,12 @@ pub struct Book {
     /// Link to view the book at the third-party service
     pub provider_link: String,
     pub rating: Option<Rating>,
+    /// Title of the book, empty if not provided by the third-party service
+    pub title: String,
+    /// Authors of the book, empty if not provided by the third-party service
+    pub authors: Vec<String>,
+    /// Identifiers for the book across the third-party services it was retrieved from
+    pub identifiers: Identifiers,
 }
 
 /// Rating data retrieved from third-party services.
@@ -139,27 +194,76 @@ pub struct Rating {
     pub ratings_count: u32,
 }
 
+/// Identifiers for a [Book](struct@Book), across the third-party services it was retrieved
+/// from.
+///
+/// All fields are optional, since not every provider returns every identifier, and a book
+/// that was only retrieved from one provider will not have identifiers from the others.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Identifiers {
+    /// 10-digit ISBN
+    pub isbn_10: Option<String>,
+    /// 13-digit ISBN
+    pub isbn_13: Option<String>,
+    /// OpenLibrary ID (OLID) of the edition
+    pub olid: Option<String>,
+    /// Google Books volume id
+    pub google_volume_id: Option<String>,
+}
+
+impl Identifiers {
+    /// Returns new identifiers. Any identifier not available can be passed as [`None`](None).
+    pub fn new(
+        isbn_10: Option<String>,
+        isbn_13: Option<String>,
+        olid: Option<String>,
+        google_volume_id: Option<String>,
+    ) -> Self {
+        Self {
+            isbn_10,
+            isbn_13,
+            olid,
+            google_volume_id,
+        }
+    }
+}
+
 impl Book {
     /// Returns a Book with defaults for optional data.
     ///
     /// - rating is optional, and by default is [`None`](None)
-    pub fn new(page_count: u32, description: &str, provider_link: &str) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        page_count: u32,
+        description: &str,
+        provider_link: &str,
+        title: &str,
+        authors: Vec<String>,
+        identifiers: Identifiers,
+    ) -> Self {
         Self {
             page_count,
             description: String::from(description),
             provider_link: String::from(provider_link),
+            title: String::from(title),
+            authors,
+            identifiers,
             rating: None,
         }
     }
 
     /// Returns a Book with required data and ratings
+    #[allow(clippy::too_many_arguments)]
     pub fn new_with_rating(
         page_count: u32,
         description: &str,
         provider_link: &str,
+        title: &str,
+        authors: Vec<String>,
+        identifiers: Identifiers,
         rating: Rating,
     ) -> Self {
-        let mut book = Book::new(page_count, description, provider_link);
+        let mut book = Book::new(page_count, description, provider_link, title, authors, identifiers);
         book.rating = Some(rating);
         book
     }
@@ -187,13 +291,17 @@ impl Rating {
 /// This trait provides different ways of which the data can be retrieved.
 ///
 /// In cases where a third-party API does not support one of the ways to retrieve data,
-/// then `unimplemented!` is used, to indicate that
+/// [`ClientError::Unsupported`] is returned, to indicate that
 /// a [Book](struct@Book) cannot not be queried using that functionality.
 ///
 /// When there's an error with communication/network, and the request cannot be completed,
 /// the rate limit has been reached, the book could not be found,
 /// or a HTTP status code has been returned that is not 200, then an error will be returned.
-#[async_trait]
+///
+/// With the `blocking` feature enabled, every method on this trait becomes synchronous
+/// instead, so implementations can be used outside of an async runtime.
+#[cfg_attr(not(feature = "blocking"), async_trait)]
+#[maybe_async::maybe_async]
 pub trait BookClient {
     /// Returns a book from the given ISBN.
     async fn book_by_isbn(&self, isbn: &str) -> Result<Book, ClientError>;
@@ -202,6 +310,7 @@ pub trait BookClient {
     async fn book(&self, author: &str, title: &str) -> Result<Book, ClientError>;
 }
 
+#[cfg(not(feature = "blocking"))]
 fn create_http_client() -> Result<reqwest::Client, reqwest::Error> {
     let version: &str = option_env!("CARGO_PKG_VERSION").unwrap_or("1.0.0");
 
@@ -212,3 +321,15 @@ fn create_http_client() -> Result<reqwest::Client, reqwest::Error> {
         .connect_timeout(Duration::from_secs(30))
         .build()
 }
+
+#[cfg(feature = "blocking")]
+fn create_http_client() -> Result<reqwest::blocking::Client, reqwest::Error> {
+    let version: &str = option_env!("CARGO_PKG_VERSION").unwrap_or("1.0.0");
+
+    reqwest::blocking::Client::builder()
+        .gzip(true)
+        .user_agent(format!("zana/{} (gzip)", version))
+        .timeout(Duration::from_secs(30))
+        .connect_timeout(Duration::from_secs(30))
+        .build()
+}
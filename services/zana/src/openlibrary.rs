@@ -0,0 +1,385 @@
+/*!
+Queries book data from OpenLibrary API using the [`Client`](struct@Client)
+implementation of [`BookClient`](trait@BookClient).
+
+It queries up to three separate endpoints to retrieve data about a book:
+1. The `isbn` endpoint, to fetch the edition of the book
+2. The `works` endpoint, to fetch the work the edition belongs to (for its description)
+3. The `ratings` endpoint of the work, to fetch the book's rating
+
+The latter two calls can be skipped with [`BookOptions`](struct@BookOptions) and
+[`Client::book_by_isbn_opts`](Client::book_by_isbn_opts), for callers that only need basic
+metadata.
+
+See example [here](../index.html#example-1).
+ */
+#[cfg(not(feature = "blocking"))]
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::retry::{self, RetryConfig};
+use crate::{create_http_client, Book, BookClient, ClientError, HttpClient, Identifiers, Rating};
+
+const ISBN_PATH: &str = "/isbn";
+
+#[derive(Deserialize, Debug)]
+struct Edition {
+    #[serde(default)]
+    key: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    by_statement: Option<String>,
+    #[serde(default)]
+    isbn_10: Vec<String>,
+    #[serde(default)]
+    isbn_13: Vec<String>,
+    #[serde(default)]
+    works: Vec<WorkRef>,
+    #[serde(default, rename(deserialize = "number_of_pages"))]
+    page_count: u32,
+}
+
+impl Edition {
+    /// Authors of the edition, parsed from its free-text `by_statement` (e.g. `"by Jane Doe"`),
+    /// since resolving author keys to names would require an additional API call per author.
+    fn authors(&self) -> Vec<String> {
+        self.by_statement
+            .as_deref()
+            .map(|by_statement| {
+                by_statement
+                    .trim_start_matches("by ")
+                    .split(',')
+                    .map(|author| author.trim().to_string())
+                    .filter(|author| !author.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The OpenLibrary ID (OLID) of this edition, the last path segment of its `key`
+    /// (e.g. `"/books/OL12345M"` -> `"OL12345M"`).
+    fn olid(&self) -> Option<String> {
+        self.key.rsplit('/').next().map(String::from).filter(|olid| !olid.is_empty())
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct WorkRef {
+    key: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct Work {
+    #[serde(default)]
+    description: Option<Description>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum Description {
+    Plain(String),
+    Detailed { value: String },
+}
+
+impl Description {
+    fn into_string(self) -> String {
+        match self {
+            Description::Plain(value) => value,
+            Description::Detailed { value } => value,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct Ratings {
+    summary: RatingsSummary,
+}
+
+#[derive(Deserialize, Debug)]
+struct RatingsSummary {
+    average: Option<f32>,
+    #[serde(default)]
+    count: u32,
+}
+
+/// Options to control which of OpenLibrary's sub-calls are made when fetching a book.
+///
+/// The edition lookup by ISBN is always made, since it is the only way to resolve a book
+/// from an ISBN in the first place. `fetch_work` and `fetch_ratings` let callers that only
+/// need basic metadata (e.g. page count or the provider link) skip the other two calls,
+/// cutting latency and quota usage by up to two-thirds.
+#[derive(Debug, Clone, Copy)]
+pub struct BookOptions {
+    /// Whether to fetch the work linked to the edition, used for `description`.
+    /// When `false`, `description` is left empty.
+    pub fetch_work: bool,
+    /// Whether to fetch the ratings of the work linked to the edition.
+    /// When `false`, `rating` is left as [`None`](None).
+    pub fetch_ratings: bool,
+}
+
+impl BookOptions {
+    /// Returns new book options.
+    pub fn new(fetch_work: bool, fetch_ratings: bool) -> Self {
+        Self {
+            fetch_work,
+            fetch_ratings,
+        }
+    }
+}
+
+impl Default for BookOptions {
+    /// Fetches everything: the work and its ratings.
+    fn default() -> Self {
+        Self::new(true, true)
+    }
+}
+
+/// Returns `work_key` if a sub-call it gates is both linked to the edition and enabled,
+/// `None` otherwise.
+fn resolve_sub_call_key(work_key: &Option<String>, enabled: bool) -> Option<&str> {
+    match (work_key, enabled) {
+        (Some(work_key), true) => Some(work_key.as_str()),
+        _ => None,
+    }
+}
+
+/// Client used to retrieve data from OpenLibrary.
+pub struct Client {
+    api_url: String,
+    http_client: HttpClient,
+    retry_config: RetryConfig,
+}
+
+impl Client {
+    /// Returns a new client that will make requests to the given API URL.
+    ///
+    /// `retry_config` controls how the client retries requests that are rate limited or
+    /// fail transiently, see [RetryConfig](crate::retry::RetryConfig).
+    pub fn new(api_url: &str, retry_config: RetryConfig) -> Result<Self, ClientError> {
+        let http_client = create_http_client()?;
+        Ok(Client {
+            api_url: String::from(api_url),
+            http_client,
+            retry_config,
+        })
+    }
+
+    #[maybe_async::maybe_async]
+    async fn fetch_edition(&self, isbn: &str) -> Result<Edition, ClientError> {
+        let url = format!("{}{}/{}.json", self.api_url, ISBN_PATH, isbn);
+        self.fetch_with_retry(&url, |status_code| status_code == 404).await
+    }
+
+    #[maybe_async::maybe_async]
+    async fn fetch_work(&self, work_key: &str) -> Result<Work, ClientError> {
+        let url = format!("{}{}.json", self.api_url, work_key);
+        self.fetch_with_retry(&url, |_| false).await
+    }
+
+    #[maybe_async::maybe_async]
+    async fn fetch_ratings(&self, work_key: &str) -> Result<Ratings, ClientError> {
+        let url = format!("{}{}/ratings.json", self.api_url, work_key);
+        self.fetch_with_retry(&url, |_| false).await
+    }
+
+    /// Fetches and deserializes the given URL, retrying on `429`/`503` responses and on
+    /// network/timeout errors, per [RetryConfig](crate::retry::RetryConfig).
+    ///
+    /// `is_not_found` lets callers map a provider-specific "not found" status code, since
+    /// OpenLibrary does not use the same status code for every endpoint.
+    #[maybe_async::maybe_async]
+    async fn fetch_with_retry<T, F>(&self, url: &str, is_not_found: F) -> Result<T, ClientError>
+    where
+        T: serde::de::DeserializeOwned,
+        F: Fn(u16) -> bool,
+    {
+        let mut attempt = 0;
+        loop {
+            let response = self.http_client.get(url).send().await;
+
+            let response = match response {
+                Ok(response) => response,
+                Err(_) if attempt < self.retry_config.max_retries => {
+                    retry::sleep(retry::backoff_delay(&self.retry_config, attempt)).await;
+                    attempt += 1;
+                    continue;
+                }
+                Err(err) => return Err(ClientError::InternalClient(err)),
+            };
+
+            let status_code = response.status().as_u16();
+            if is_not_found(status_code) {
+                return Err(ClientError::NotFound);
+            } else if status_code == 429 || status_code == 503 {
+                if attempt >= self.retry_config.max_retries {
+                    return Err(ClientError::RateLimitExceeded);
+                }
+                let delay = retry::retry_after_delay(response.headers())
+                    .unwrap_or_else(|| retry::backoff_delay(&self.retry_config, attempt));
+                retry::sleep(delay).await;
+                attempt += 1;
+                continue;
+            } else if status_code < 200 || status_code >= 300 {
+                let response_body = response.text().await?;
+                return Err(ClientError::Http(status_code, response_body));
+            }
+
+            return Ok(response.json().await?);
+        }
+    }
+
+    /// Returns a book by ISBN, fetching only the sub-calls enabled by `options`.
+    ///
+    /// The edition is always fetched by ISBN. When `options.fetch_work` is `false`, the work
+    /// is not fetched and `description` is left empty. When `options.fetch_ratings` is
+    /// `false`, ratings are not fetched and `rating` is left as [`None`](None).
+    #[maybe_async::maybe_async]
+    pub async fn book_by_isbn_opts(&self, isbn: &str, options: &BookOptions) -> Result<Book, ClientError> {
+        let edition = self.fetch_edition(isbn).await?;
+        let work_key = edition.works.first().map(|work_ref| work_ref.key.clone());
+
+        let description = match resolve_sub_call_key(&work_key, options.fetch_work) {
+            Some(work_key) => {
+                let work = self.fetch_work(work_key).await?;
+                work.description.map(Description::into_string).unwrap_or_default()
+            }
+            None => String::new(),
+        };
+
+        let rating = match resolve_sub_call_key(&work_key, options.fetch_ratings) {
+            Some(work_key) => {
+                let ratings = self.fetch_ratings(work_key).await?;
+                ratings
+                    .summary
+                    .average
+                    .map(|average| Rating::new(average, ratings.summary.count))
+            }
+            None => None,
+        };
+
+        let provider_link = format!(
+            "{}{}",
+            self.api_url,
+            work_key.unwrap_or_else(|| format!("{}/{}", ISBN_PATH, isbn))
+        );
+
+        let identifiers = Identifiers::new(
+            edition.isbn_10.first().cloned(),
+            edition.isbn_13.first().cloned(),
+            edition.olid(),
+            None,
+        );
+        let authors = edition.authors();
+
+        Ok(match rating {
+            Some(rating) => Book::new_with_rating(
+                edition.page_count,
+                &description,
+                &provider_link,
+                &edition.title,
+                authors,
+                identifiers,
+                rating,
+            ),
+            None => Book::new(
+                edition.page_count,
+                &description,
+                &provider_link,
+                &edition.title,
+                authors,
+                identifiers,
+            ),
+        })
+    }
+}
+
+#[cfg_attr(not(feature = "blocking"), async_trait)]
+#[maybe_async::maybe_async]
+impl BookClient for Client {
+    /// Returns a book by ISBN.
+    ///
+    /// The edition is fetched first by ISBN, then its work is fetched for the description,
+    /// and finally the work's ratings are fetched. If no work is linked to the edition,
+    /// the description and rating are left empty.
+    ///
+    /// Equivalent to [`book_by_isbn_opts`](Client::book_by_isbn_opts) with
+    /// [`BookOptions::default()`](BookOptions::default).
+    async fn book_by_isbn(&self, isbn: &str) -> Result<Book, ClientError> {
+        self.book_by_isbn_opts(isbn, &BookOptions::default()).await
+    }
+
+    /// OpenLibrary has no endpoint to query a book by author and title, only by ISBN, so this
+    /// always returns [`ClientError::Unsupported`].
+    async fn book(&self, _author: &str, _title: &str) -> Result<Book, ClientError> {
+        Err(ClientError::Unsupported(
+            "OpenLibrary client does not support querying a book by author and title",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edition(key: &str, by_statement: Option<&str>) -> Edition {
+        Edition {
+            key: String::from(key),
+            title: String::new(),
+            by_statement: by_statement.map(String::from),
+            isbn_10: vec![],
+            isbn_13: vec![],
+            works: vec![],
+            page_count: 0,
+        }
+    }
+
+    #[test]
+    fn olid_returns_the_last_path_segment_of_the_key() {
+        assert_eq!(edition("/books/OL12345M", None).olid(), Some(String::from("OL12345M")));
+    }
+
+    #[test]
+    fn olid_is_none_for_an_empty_key() {
+        assert_eq!(edition("", None).olid(), None);
+    }
+
+    #[test]
+    fn authors_is_empty_without_a_by_statement() {
+        assert_eq!(edition("/books/OL12345M", None).authors(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn authors_parses_a_single_author() {
+        assert_eq!(
+            edition("/books/OL12345M", Some("by Jane Doe")).authors(),
+            vec![String::from("Jane Doe")]
+        );
+    }
+
+    #[test]
+    fn authors_parses_multiple_comma_separated_authors() {
+        assert_eq!(
+            edition("/books/OL12345M", Some("by Jane Doe, John Smith")).authors(),
+            vec![String::from("Jane Doe"), String::from("John Smith")]
+        );
+    }
+
+    #[test]
+    fn resolve_sub_call_key_is_none_without_a_linked_work() {
+        assert_eq!(resolve_sub_call_key(&None, true), None);
+    }
+
+    #[test]
+    fn resolve_sub_call_key_is_none_when_disabled() {
+        let work_key = Some(String::from("/works/OL123W"));
+        assert_eq!(resolve_sub_call_key(&work_key, false), None);
+    }
+
+    #[test]
+    fn resolve_sub_call_key_is_some_when_linked_and_enabled() {
+        let work_key = Some(String::from("/works/OL123W"));
+        assert_eq!(resolve_sub_call_key(&work_key, true), Some("/works/OL123W"));
+    }
+}
@@ -0,0 +1,141 @@
+/*!
+Retry-with-backoff behavior shared by [`googlebooks::Client`](crate::googlebooks::Client) and
+[`openlibrary::Client`](crate::openlibrary::Client).
+
+On a `429` or `503` response, the `Retry-After` header is honored when present (either an
+integer number of seconds, or an HTTP-date). When the header is absent, or the error is a
+network/timeout error from [reqwest](reqwest), an exponential backoff with full jitter is used
+instead: `delay = random_between(0, min(max_backoff, initial_backoff * 2^attempt))`.
+*/
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::header::HeaderMap;
+
+/// Configuration for the retry-with-backoff behavior used by both clients.
+///
+/// [max_retries](RetryConfig::max_retries) is the number of times a request is retried after
+/// its first attempt, so a total of `max_retries + 1` attempts are made before giving up.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryConfig {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RetryConfig {
+    /// Returns a new retry configuration.
+    pub fn new(max_retries: u32, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            initial_backoff,
+            max_backoff,
+        }
+    }
+}
+
+impl Default for RetryConfig {
+    /// Defaults to 3 retries, starting at 500ms and capped at 30 seconds.
+    fn default() -> Self {
+        Self::new(3, Duration::from_millis(500), Duration::from_secs(30))
+    }
+}
+
+/// Returns the delay requested by a `Retry-After` response header, if present.
+///
+/// Supports both forms allowed by the HTTP spec: an integer number of seconds, or an HTTP-date.
+pub(crate) fn retry_after_delay(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let retry_at = httpdate::parse_http_date(value).ok()?;
+    retry_at.duration_since(std::time::SystemTime::now()).ok()
+}
+
+/// Returns an exponential backoff delay with full jitter for the given attempt number.
+///
+/// `delay = random_between(0, min(max_backoff, initial_backoff * 2^attempt))`
+pub(crate) fn backoff_delay(config: &RetryConfig, attempt: u32) -> Duration {
+    let max_delay_millis = (config.initial_backoff.as_millis() as u64)
+        .saturating_mul(2u64.saturating_pow(attempt))
+        .min(config.max_backoff.as_millis() as u64);
+
+    let jittered_millis = rand::thread_rng().gen_range(0..=max_delay_millis);
+    Duration::from_millis(jittered_millis)
+}
+
+/// Sleeps for the given duration, asynchronously unless the `blocking` feature is enabled.
+#[maybe_async::maybe_async]
+pub(crate) async fn sleep(duration: Duration) {
+    #[cfg(not(feature = "blocking"))]
+    tokio::time::sleep(duration).await;
+    #[cfg(feature = "blocking")]
+    std::thread::sleep(duration);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+
+    use super::*;
+
+    #[test]
+    fn retry_after_delay_parses_integer_seconds() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+
+        assert_eq!(retry_after_delay(&headers), Some(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn retry_after_delay_parses_http_date() {
+        let retry_at = SystemTime::now() + Duration::from_secs(60);
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_str(&httpdate::fmt_http_date(retry_at)).unwrap(),
+        );
+
+        let delay = retry_after_delay(&headers).expect("a delay should be parsed");
+        assert!(delay <= Duration::from_secs(60) && delay > Duration::from_secs(55));
+    }
+
+    #[test]
+    fn retry_after_delay_is_none_for_a_past_date() {
+        let retry_at = SystemTime::now() - Duration::from_secs(60);
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_str(&httpdate::fmt_http_date(retry_at)).unwrap(),
+        );
+
+        assert_eq!(retry_after_delay(&headers), None);
+    }
+
+    #[test]
+    fn retry_after_delay_is_none_without_the_header() {
+        assert_eq!(retry_after_delay(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn backoff_delay_respects_the_max_backoff_cap() {
+        let config = RetryConfig::new(10, Duration::from_millis(500), Duration::from_secs(5));
+
+        for attempt in 0..10 {
+            assert!(backoff_delay(&config, attempt) <= config.max_backoff);
+        }
+    }
+
+    #[test]
+    fn backoff_delay_is_at_most_initial_backoff_on_the_first_attempt() {
+        let config = RetryConfig::new(10, Duration::from_millis(100), Duration::from_secs(30));
+
+        // At attempt 0 the delay is jittered between 0 and initial_backoff.
+        assert!(backoff_delay(&config, 0) <= Duration::from_millis(100));
+    }
+}